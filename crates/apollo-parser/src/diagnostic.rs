@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single labeled span: a byte range plus the note attached to it.
+///
+/// A `Diagnostic`'s `primary` label is "here's the problem"; any `secondary`
+/// labels are "and here's why", e.g. the construct that expected something
+/// which wasn't there.
+#[derive(Clone, Debug)]
+pub struct Label {
+    pub start: usize,
+    pub end: usize,
+    pub note: Option<String>,
+}
+
+impl Label {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            note: None,
+        }
+    }
+
+    pub fn with_note(start: usize, end: usize, note: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            note: Some(note.into()),
+        }
+    }
+}
+
+/// A parser diagnostic: a severity, a stable error code, a primary message
+/// and span, and any number of secondary labels pointing at related spans.
+///
+/// `Display` only renders the primary span and message, reproducing the
+/// single-line `ERROR@start:end "message"` format the parser's snapshot
+/// tests already assert on. Downstream tools that want carets or fix
+/// suggestions pointing at more than one place should walk `secondary`
+/// directly instead of relying on `Display`.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, primary: Label, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            primary,
+            secondary: Vec::new(),
+        }
+    }
+
+    pub fn with_secondary(mut self, label: Label) -> Self {
+        self.secondary.push(label);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ERROR@{}:{} \"{}\"",
+            self.primary.start, self.primary.end, self.message
+        )
+    }
+}
@@ -0,0 +1,189 @@
+use crate::SyntaxKind;
+
+/// The kind of a single lexical token.
+///
+/// GraphQL keywords (`type`, `input`, `enum`, ...) aren't reserved words —
+/// they're valid names too — so the lexer never hands back a dedicated
+/// keyword variant for them. It always emits [`TokenKind::Name`];
+/// `Parser::at_keyword` is how a grammar function treats a `Name` as a
+/// keyword when it's in a position where only a keyword makes sense.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Name,
+    Int,
+    Float,
+    String,
+    Comma,
+    Colon,
+    Equals,
+    At,
+    Bang,
+    LCurly,
+    RCurly,
+    LBracket,
+    RBracket,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) data: String,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl From<TokenKind> for SyntaxKind {
+    fn from(kind: TokenKind) -> SyntaxKind {
+        match kind {
+            TokenKind::Name => SyntaxKind::IDENT,
+            TokenKind::Int => SyntaxKind::INT_VALUE,
+            TokenKind::Float => SyntaxKind::FLOAT_VALUE,
+            TokenKind::String => SyntaxKind::STRING_VALUE,
+            TokenKind::Comma => SyntaxKind::COMMA,
+            TokenKind::Colon => SyntaxKind::COLON,
+            TokenKind::Equals => SyntaxKind::EQ,
+            TokenKind::At => SyntaxKind::AT,
+            TokenKind::Bang => SyntaxKind::BANG,
+            TokenKind::LCurly => SyntaxKind::L_CURLY,
+            TokenKind::RCurly => SyntaxKind::R_CURLY,
+            TokenKind::LBracket => SyntaxKind::L_BRACKET,
+            TokenKind::RBracket => SyntaxKind::R_BRACKET,
+        }
+    }
+}
+
+/// Tokenizes `input` into a flat `Vec<Token>`.
+///
+/// Keywords are lexed as plain `Name` tokens (see [`TokenKind`]); nothing
+/// here knows or cares that "input" or "type" are keywords at all — that's
+/// entirely a parser-side, position-dependent concern.
+pub(crate) fn lex(input: &str) -> Vec<Token> {
+    let bytes = input.as_bytes();
+    let mut pos = 0;
+    let mut tokens = Vec::new();
+
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos] as char;
+
+        if c.is_whitespace() {
+            pos += 1;
+            continue;
+        }
+
+        let (kind, len) = match c {
+            '{' => (TokenKind::LCurly, 1),
+            '}' => (TokenKind::RCurly, 1),
+            '[' => (TokenKind::LBracket, 1),
+            ']' => (TokenKind::RBracket, 1),
+            '@' => (TokenKind::At, 1),
+            ':' => (TokenKind::Colon, 1),
+            '=' => (TokenKind::Equals, 1),
+            '!' => (TokenKind::Bang, 1),
+            ',' => (TokenKind::Comma, 1),
+            '"' => {
+                let mut end = pos + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += 1;
+                }
+                end = (end + 1).min(bytes.len());
+                (TokenKind::String, end - pos)
+            }
+            // A leading `-` only starts a number if it's actually followed by
+            // a digit; otherwise it falls through to the catch-all below
+            // like any other unrecognized character.
+            c if c.is_ascii_digit()
+                || (c == '-' && matches!(bytes.get(pos + 1), Some(b) if b.is_ascii_digit())) =>
+            {
+                let mut end = pos + if c == '-' { 1 } else { 0 };
+                let mut is_float = false;
+                // Unlike the leading sign, a `-` can't continue a number
+                // mid-scan: only the first character may be one, or
+                // `"12-3"` would lex as a single bogus Int token instead of
+                // two separate ones.
+                while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+                    if bytes[end] == b'.' {
+                        is_float = true;
+                    }
+                    end += 1;
+                }
+                (
+                    if is_float {
+                        TokenKind::Float
+                    } else {
+                        TokenKind::Int
+                    },
+                    end - pos,
+                )
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = pos;
+                while end < bytes.len()
+                    && ((bytes[end] as char).is_alphanumeric() || bytes[end] == b'_')
+                {
+                    end += 1;
+                }
+                (TokenKind::Name, end - pos)
+            }
+            _ => {
+                pos += 1;
+                continue;
+            }
+        };
+
+        let end = start + len;
+        tokens.push(Token {
+            kind,
+            data: input[start..end].to_string(),
+            start,
+            end,
+        });
+        pos = end;
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_lexes_negative_integers() {
+        let tokens = lex("-5");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenKind::Int);
+        assert_eq!(tokens[0].data, "-5");
+    }
+
+    #[test]
+    fn it_lexes_negative_floats() {
+        let tokens = lex("-5.5");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenKind::Float);
+        assert_eq!(tokens[0].data, "-5.5");
+    }
+
+    #[test]
+    fn it_does_not_let_a_dash_continue_a_number_mid_scan() {
+        // A `-` only starts a new number; it must not glue two adjacent
+        // numbers together into one bogus token.
+        let tokens = lex("12-3");
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].kind, TokenKind::Int);
+        assert_eq!(tokens[0].data, "12");
+        assert_eq!(tokens[1].kind, TokenKind::Int);
+        assert_eq!(tokens[1].data, "-3");
+    }
+
+    #[test]
+    fn it_does_not_treat_a_lone_dash_as_a_number() {
+        // A `-` not followed by a digit isn't a number at all; it falls
+        // through to the catch-all and is silently skipped, same as any
+        // other unrecognized character.
+        let tokens = lex("- foo");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, TokenKind::Name);
+        assert_eq!(tokens[0].data, "foo");
+    }
+}
@@ -0,0 +1,23 @@
+use crate::parser::CompletedMarker;
+use crate::{Parser, SyntaxKind, TokenKind};
+
+/// See: https://spec.graphql.org/June2018/#Description
+///
+/// ```txt
+/// Description
+///     StringValue
+/// ```
+///
+/// Parses an optional leading description string, returning the completed
+/// `DESCRIPTION` node if one was present. Callers that can be preceded by a
+/// description parse it *before* they know their own node's kind, then use
+/// [`CompletedMarker::precede`] to retroactively wrap it once they do.
+pub(crate) fn opt_description(p: &mut Parser) -> Option<CompletedMarker> {
+    if p.peek() != Some(TokenKind::String) {
+        return None;
+    }
+
+    let m = p.start();
+    p.bump(SyntaxKind::STRING_VALUE);
+    Some(m.complete(p, SyntaxKind::DESCRIPTION))
+}
@@ -1,28 +1,107 @@
-use crate::parser::grammar::{directive, name, ty, value};
-use crate::{create_err, Parser, SyntaxKind, TokenKind, S, T};
+use crate::parser::grammar::{description, directive, name, ty, value};
+use crate::parser::token_set::TokenSet;
+use crate::{Diagnostic, Label, Parser, SyntaxKind, TokenKind, S, T};
 
-/// See: https://spec.graphql.org/June2018/#InputObjectTypeDefinition
+/// Structural tokens that a definition list (schema, `type`, `input`, etc.)
+/// can always recover on: a stray closing brace belonging to an enclosing
+/// block, or the end of input.
 ///
-/// ```txt
-/// InputObjectTypeDefinition
-///     Description[opt] input Name Directives[Const][opt] InputFieldsDefinition[opt]
-/// ```
-pub(crate) fn input_object_type_definition(p: &mut Parser) {
-    let _guard = p.start_node(SyntaxKind::INPUT_OBJECT_TYPE_DEFINITION);
-    p.bump(SyntaxKind::input_KW);
+/// When a grammar function runs into a token it doesn't expect, it skips
+/// forward until it reaches one of these, or [`at_definition_keyword`], instead
+/// of recursing into itself, so a single malformed construct produces one
+/// diagnostic rather than a flood of them.
+const DEFINITION_RECOVERY_SET: TokenSet = TokenSet::new(&[TokenKind::RCurly]);
+
+/// Keywords that start a new top-level definition. These aren't reserved
+/// words in GraphQL (a field or argument can legally be named `type` or
+/// `input`), so the lexer hands them back as ordinary `Name` tokens; only a
+/// definition-leading position treats them as keywords.
+const DEFINITION_KEYWORDS: &[&str] = &[
+    "type",
+    "input",
+    "enum",
+    "scalar",
+    "interface",
+    "union",
+    "schema",
+    "directive",
+    "extend",
+];
 
+/// Whether the upcoming `Name` is one of [`DEFINITION_KEYWORDS`], i.e. this is
+/// a safe place to stop skipping stray tokens because a new top-level
+/// definition is about to start.
+fn at_definition_keyword(p: &Parser) -> bool {
+    matches!(p.peek(), Some(TokenKind::Name)) && DEFINITION_KEYWORDS.iter().any(|kw| p.at_keyword(kw))
+}
+
+/// Parses a `Name`, recording a diagnostic and skipping forward to
+/// `recovery_set` (or the start of the next definition) if the current token
+/// isn't one, instead of leaving the cursor where it is.
+///
+/// This keeps a missing name from stalling the parser in the middle of a
+/// block: the caller decides, via `recovery_set`, how far it's safe to look
+/// for the next token that can resume parsing. `keyword_span` is the real
+/// byte range of the `input` keyword already bumped by the caller (captured
+/// via `p.offset()`/`p.prev_end()` around that bump), and becomes the
+/// diagnostic's primary label, since that's the construct actually missing a
+/// name, not whatever token happens to follow it.
+fn name_r(p: &mut Parser, keyword_span: (usize, usize), recovery_set: TokenSet) {
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
         _ => {
-            p.push_err(create_err!(
-                p.peek_data()
-                    .unwrap_or_else(|| String::from("no further data")),
-                "Expected Input Object Type Definition to have a Name, got {}",
-                p.peek_data()
-                    .unwrap_or_else(|| String::from("no further data")),
+            p.push_err(Diagnostic::error(
+                "E0010",
+                Label::new(keyword_span.0, keyword_span.1),
+                format!(
+                    "Expected Input Object Type Definition to have a Name, got {}",
+                    p.peek_data()
+                        .unwrap_or_else(|| String::from("no further data"))
+                ),
             ));
+
+            // Only open an ERROR node when there's a stray token to wrap, so
+            // a name directly followed by `{` or `@` leaves no empty node.
+            if p.peek().map_or(false, |kind| !recovery_set.contains(kind)) {
+                let m = p.start();
+                while let Some(kind) = p.peek() {
+                    if recovery_set.contains(kind) || at_definition_keyword(p) {
+                        break;
+                    }
+                    p.bump_any();
+                }
+                m.complete(p, SyntaxKind::ERROR);
+            }
         }
     }
+}
+
+/// See: https://spec.graphql.org/June2018/#InputObjectTypeDefinition
+///
+/// ```txt
+/// InputObjectTypeDefinition
+///     Description[opt] input Name Directives[Const][opt] InputFieldsDefinition[opt]
+/// ```
+pub(crate) fn input_object_type_definition(p: &mut Parser) {
+    // The optional leading Description is parsed before this function's own
+    // node kind is decided, so it's wrapped via `precede` instead of a plain
+    // `p.start()`: `opt_description` has no way to know it's about to be
+    // followed by an input object definition rather than, say, a `type`.
+    let description = description::opt_description(p);
+    let m = match description {
+        Some(description) => description.precede(p),
+        None => p.start(),
+    };
+
+    let keyword_start = p.offset();
+    p.bump_remap(SyntaxKind::input_KW);
+    let keyword_span = (keyword_start, p.prev_end());
+
+    name_r(
+        p,
+        keyword_span,
+        DEFINITION_RECOVERY_SET.union(TokenSet::new(&[T![@], T!['{']])),
+    );
 
     if let Some(T![@]) = p.peek() {
         directive::directives(p);
@@ -31,6 +110,8 @@ pub(crate) fn input_object_type_definition(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
         input_fields_definition(p);
     }
+
+    m.complete(p, SyntaxKind::INPUT_OBJECT_TYPE_DEFINITION);
 }
 
 /// See: https://spec.graphql.org/June2018/#InputObjectTypeExtension
@@ -41,24 +122,22 @@ pub(crate) fn input_object_type_definition(p: &mut Parser) {
 ///     extend input Name Directives[Const]
 /// ```
 pub(crate) fn input_object_type_extension(p: &mut Parser) {
-    let _guard = p.start_node(SyntaxKind::INPUT_OBJECT_TYPE_EXTENSION);
-    p.bump(SyntaxKind::extend_KW);
-    p.bump(SyntaxKind::input_KW);
+    let m = p.start();
+    let extend_start = p.offset();
+    p.bump_remap(SyntaxKind::extend_KW);
+
+    let input_start = p.offset();
+    p.bump_remap(SyntaxKind::input_KW);
+    let input_span = (input_start, p.prev_end());
+    let extend_input_span = (extend_start, p.prev_end());
 
     let mut meets_requirements = false;
 
-    match p.peek() {
-        Some(TokenKind::Name) => name::name(p),
-        _ => {
-            p.push_err(create_err!(
-                p.peek_data()
-                    .unwrap_or_else(|| String::from("no further data")),
-                "Expected Input Object Type Definition to have a Name, got {}",
-                p.peek_data()
-                    .unwrap_or_else(|| String::from("no further data")),
-            ));
-        }
-    }
+    name_r(
+        p,
+        input_span,
+        DEFINITION_RECOVERY_SET.union(TokenSet::new(&[T![@], T!['{']])),
+    );
 
     if let Some(T![@]) = p.peek() {
         meets_requirements = true;
@@ -71,16 +150,29 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.push_err(create_err!(
-            p
-                .peek_data()
-                .unwrap_or_else(|| String::from("no further data")),
-            "Expected Input Object Type Extension to have Directives or Input Fields Definition, got {}",
-            p
-                .peek_data()
-                .unwrap_or_else(|| String::from("no further data")),
-        ));
+        let got = p.peek_data();
+        let got_display = got
+            .clone()
+            .unwrap_or_else(|| String::from("no further data"));
+        let got_start = p.offset();
+        let got_end = got_start + got.map(|data| data.len()).unwrap_or(0);
+        p.push_err(
+            Diagnostic::error(
+                "E0011",
+                Label::new(extend_input_span.0, extend_input_span.1),
+                format!(
+                    "Expected Input Object Type Extension to have Directives or Input Fields Definition, got {got_display}"
+                ),
+            )
+            .with_secondary(Label::with_note(
+                got_start,
+                got_end,
+                format!("expected `{{` or `@` here, found {got_display}"),
+            )),
+        );
     }
+
+    m.complete(p, SyntaxKind::INPUT_OBJECT_TYPE_EXTENSION);
 }
 
 /// See: https://spec.graphql.org/June2018/#InputFieldsDefinition
@@ -90,20 +182,93 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
 ///     { InputValueDefinition[list] }
 /// ```
 pub(crate) fn input_fields_definition(p: &mut Parser) {
-    let _guard = p.start_node(SyntaxKind::INPUT_FIELDS_DEFINITION);
+    let m = p.start();
     p.bump(S!['{']);
-    input_value_definition(p, false);
+
+    let recovery_set = DEFINITION_RECOVERY_SET;
+    let mut has_fields = false;
+    loop {
+        match p.peek() {
+            // A field always looks like `Name :`, description aside — so a
+            // `Name` is only treated as one when it's actually followed by a
+            // `:`. That's what lets a field legitimately named `type` parse
+            // as a field while a stray `type Query { ... }` (from a missing
+            // `}` above) still falls through to the recovery arm below and
+            // stops instead of being swallowed as junk field names.
+            Some(TokenKind::Name) if p.nth(1) == Some(TokenKind::Colon) => {
+                has_fields = true;
+                input_value_definition(p, recovery_set);
+            }
+            Some(T!['}']) | None => break,
+            _ => {
+                // A whole run of stray tokens is one malformed field, so it
+                // gets one diagnostic covering the full run, not one per
+                // token skipped.
+                let stray_start = p.offset();
+                let err = p.start();
+                // Always consume the first stray token before checking
+                // whether it's safe to stop: if it's itself a recovery token
+                // or a definition keyword (e.g. this arm was reached because
+                // an unclosed block above is immediately followed by a
+                // sibling definition), checking before bumping would break
+                // without consuming anything, and the outer `loop` would
+                // just re-enter this same arm forever with the cursor
+                // unmoved.
+                p.bump_any();
+                while let Some(kind) = p.peek() {
+                    if recovery_set.contains(kind) || at_definition_keyword(p) {
+                        break;
+                    }
+                    // A field always looks like `Name :` (see the list
+                    // dispatch above) — stop here too, so a legitimate field
+                    // following a run of junk isn't swallowed into the same
+                    // ERROR node.
+                    if kind == TokenKind::Name && p.nth(1) == Some(TokenKind::Colon) {
+                        break;
+                    }
+                    p.bump_any();
+                }
+                err.complete(p, SyntaxKind::ERROR);
+                p.push_err(Diagnostic::error(
+                    "E0016",
+                    Label::new(stray_start, p.prev_end()),
+                    "Expected an InputValue definition here",
+                ));
+            }
+        }
+    }
+
+    if !has_fields {
+        let got = p.peek_data();
+        let got_display = got
+            .clone()
+            .unwrap_or_else(|| String::from("no further data"));
+        let got_start = p.offset();
+        let got_end = got_start + got.map(|data| data.len()).unwrap_or(0);
+        p.push_err(Diagnostic::error(
+            "E0012",
+            Label::new(got_start, got_end),
+            format!("Expected to have an InputValue definition, got {got_display}"),
+        ));
+    }
+
     if let Some(T!['}']) = p.peek() {
         p.bump(S!['}'])
     } else {
-        p.push_err(create_err!(
-            p.peek_data()
-                .unwrap_or_else(|| String::from("no further data")),
-            "Expected Fields Definition to have a closing }}, got {}",
-            p.peek_data()
-                .unwrap_or_else(|| String::from("no further data"))
+        let got = p.peek_data();
+        let got_display = got
+            .clone()
+            .unwrap_or_else(|| String::from("no further data"));
+        let got_start = p.offset();
+        let got_end = got_start + got.map(|data| data.len()).unwrap_or(0);
+        p.push_err(Diagnostic::error(
+            "E0013",
+            Label::new(got_start, got_end),
+            format!("Expected Fields Definition to have a closing }}, got {got_display}"),
         ));
     }
+
+    m.complete(p, SyntaxKind::INPUT_FIELDS_DEFINITION);
 }
 
 /// See: https://spec.graphql.org/June2018/#InputValueDefinition
@@ -112,50 +277,65 @@ pub(crate) fn input_fields_definition(p: &mut Parser) {
 /// InputValueDefinition
 ///     Description(opt) Name : Type DefaultValue(opt) Directives(const/opt)
 /// ```
-pub(crate) fn input_value_definition(p: &mut Parser, is_input: bool) {
-    if let Some(TokenKind::Name) = p.peek() {
-        let guard = p.start_node(SyntaxKind::INPUT_VALUE_DEFINITION);
-        name::name(p);
-        if let Some(T![:]) = p.peek() {
-            p.bump(S![:]);
-            match p.peek() {
-                Some(TokenKind::Name) | Some(T!['[']) => {
-                    ty::ty(p);
-                    if let Some(T![=]) = p.peek() {
-                        value::default_value(p);
+///
+/// Parses a single field and, if present, the trailing comma that separates
+/// it from the next one. `recovery_set` is the set of tokens the enclosing
+/// `InputFieldsDefinition` list already knows how to resume on; a missing or
+/// malformed type is reported once here rather than recursing.
+pub(crate) fn input_value_definition(p: &mut Parser, recovery_set: TokenSet) {
+    let m = p.start();
+    name::name(p);
+
+    if let Some(T![:]) = p.peek() {
+        p.bump(S![:]);
+        match p.peek() {
+            Some(TokenKind::Name) | Some(T!['[']) => {
+                ty::ty(p);
+                if let Some(T![=]) = p.peek() {
+                    value::default_value(p);
+                }
+                if let Some(T![@]) = p.peek() {
+                    directive::directives(p);
+                }
+            }
+            _ => {
+                let got = p.peek_data();
+                let got_display = got
+                    .clone()
+                    .unwrap_or_else(|| String::from("no further data"));
+                let got_start = p.offset();
+                let got_end = got_start + got.map(|data| data.len()).unwrap_or(0);
+                p.push_err(Diagnostic::error(
+                    "E0014",
+                    Label::new(got_start, got_end),
+                    format!("Expected InputValue definition to have a Type, got {got_display}"),
+                ));
+                // The name and `:` have already been consumed, so this
+                // field always makes progress; skipping stops at a recovery
+                // token (such as the list's closing `}`) or definition
+                // keyword without consuming it.
+                while let Some(kind) = p.peek() {
+                    if recovery_set.contains(kind) || at_definition_keyword(p) {
+                        break;
                     }
-                    if p.peek().is_some() {
-                        guard.finish_node();
-                        return input_value_definition(p, true);
+                    if kind == TokenKind::Name && p.nth(1) == Some(TokenKind::Colon) {
+                        break;
                     }
-                }
-                _ => {
-                    p.push_err(create_err!(
-                        p.peek_data().unwrap(),
-                        "Expected InputValue definition to have a Type, got {}",
-                        p.peek_data().unwrap()
-                    ));
+                    p.bump_any();
                 }
             }
-        } else {
-            p.push_err(create_err!(
-                p.peek_data().unwrap(),
-                "Expected InputValue definition to have a Name, got {}",
-                p.peek_data().unwrap()
-            ));
         }
+    } else {
+        // The only caller, `input_fields_definition`'s list dispatch, only
+        // reaches this function when the next two tokens are `Name :`, so
+        // `name::name` above can never land here without a `:` following it.
+        unreachable!("input_value_definition is only reached when `Name` is followed by `:`")
     }
+
+    m.complete(p, SyntaxKind::INPUT_VALUE_DEFINITION);
+
     if let Some(T![,]) = p.peek() {
         p.bump(S![,]);
-        return input_value_definition(p, is_input);
-    }
-    // TODO @lrlna: this can be simplified a little bit, and follow the pattern of FieldDefinition
-    if !is_input {
-        p.push_err(create_err!(
-            p.peek_data().unwrap(),
-            "Expected to have an InputValue definition, got {}",
-            p.peek_data().unwrap()
-        ));
     }
 }
 
@@ -225,7 +405,7 @@ mod test {
                                     - TYPE@10..10
                                         - NAMED_TYPE@10..10
                         - R_CURLY@10..11 "}"
-            - ERROR@0:1 "Expected Input Object Type Definition to have a Name, got {"
+            - ERROR@0:5 "Expected Input Object Type Definition to have a Name, got {"
             "#,
         )
     }
@@ -243,7 +423,7 @@ mod test {
                     - INPUT_FIELDS_DEFINITION@23..25
                         - L_CURLY@23..24 "{"
                         - R_CURLY@24..25 "}"
-            - ERROR@0:1 "Expected to have an InputValue definition, got }"
+            - ERROR@26:27 "Expected to have an InputValue definition, got }"
             "#,
         )
     }
@@ -299,7 +479,7 @@ mod test {
                             - TYPE@14..14
                                 - NAMED_TYPE@14..14
                         - R_CURLY@14..15 "}"
-            - ERROR@0:1 "Expected Input Object Type Definition to have a Name, got {"
+            - ERROR@7:12 "Expected Input Object Type Definition to have a Name, got {"
             "#,
         )
     }
@@ -315,8 +495,159 @@ mod test {
                     - input_KW@6..11 "input"
                     - NAME@11..29
                         - IDENT@11..29 "ExampleInputObject"
-            - ERROR@0:15 "Expected Input Object Type Extension to have Directives or Input Fields Definition, got no further data"
+            - ERROR@0:12 "Expected Input Object Type Extension to have Directives or Input Fields Definition, got no further data"
+            "#,
+        )
+    }
+
+    #[test]
+    fn it_labels_the_extension_keyword_not_the_unexpected_token() {
+        // The primary label must point at the real position of the `extend
+        // input` keywords, and the secondary label at the real position
+        // (and length) of whatever follows — not at byte 0 with the length
+        // of the "no further data" placeholder text, regardless of where in
+        // the document the extension actually sits.
+        let mut p = crate::Parser::new("extend input ExampleInputObject");
+        super::input_object_type_extension(&mut p);
+        let (_tree, errors) = p.finish();
+
+        assert_eq!(errors.len(), 1);
+        let err = &errors[0];
+        assert_eq!(err.code, "E0011");
+        assert_eq!((err.primary.start, err.primary.end), (0, 12));
+        assert_eq!(err.secondary.len(), 1);
+        let secondary = &err.secondary[0];
+        assert_eq!((secondary.start, secondary.end), (31, 31));
+        assert_eq!(
+            secondary.note.as_deref(),
+            Some("expected `{` or `@` here, found no further data")
+        );
+    }
+
+    #[test]
+    fn it_parses_a_field_literally_named_type() {
+        // `type` (and the other definition keywords) aren't reserved, so a
+        // field can be named `type` — it must parse as an ordinary field,
+        // not be mistaken for the start of a new top-level definition.
+        utils::check_ast(
+            "input ExampleInputObject {
+              type: String
+            }",
+            r#"
+            - DOCUMENT@0..30
+                - INPUT_OBJECT_TYPE_DEFINITION@0..30
+                    - input_KW@0..5 "input"
+                    - NAME@5..23
+                        - IDENT@5..23 "ExampleInputObject"
+                    - INPUT_FIELDS_DEFINITION@23..30
+                        - L_CURLY@23..24 "{"
+                        - INPUT_VALUE_DEFINITION@24..29
+                            - NAME@24..28
+                                - IDENT@24..28 "type"
+                            - COLON@28..29 ":"
+                            - TYPE@29..29
+                                - NAMED_TYPE@29..29
+                        - R_CURLY@29..30 "}"
+            "#,
+        )
+    }
+
+    #[test]
+    fn it_reports_one_diagnostic_for_a_run_of_malformed_fields() {
+        // Two stray tokens in a row between fields must collapse into a
+        // single ERROR node and a single diagnostic, not one diagnostic per
+        // stray token -- and the legitimate field that follows them must
+        // still parse as its own INPUT_VALUE_DEFINITION, not be swallowed
+        // into the same ERROR node.
+        utils::check_ast(
+            "input ExampleInputObject {
+              a: String
+              1 2
+              b: Int
+            }",
+            r#"
+            - DOCUMENT@0..31
+                - INPUT_OBJECT_TYPE_DEFINITION@0..31
+                    - input_KW@0..5 "input"
+                    - NAME@5..23
+                        - IDENT@5..23 "ExampleInputObject"
+                    - INPUT_FIELDS_DEFINITION@23..31
+                        - L_CURLY@23..24 "{"
+                        - INPUT_VALUE_DEFINITION@24..26
+                            - NAME@24..25
+                                - IDENT@24..25 "a"
+                            - COLON@25..26 ":"
+                            - TYPE@26..26
+                                - NAMED_TYPE@26..26
+                        - ERROR@26..28
+                            - INT_VALUE@26..27 "1"
+                            - INT_VALUE@27..28 "2"
+                        - INPUT_VALUE_DEFINITION@28..30
+                            - NAME@28..29
+                                - IDENT@28..29 "b"
+                            - COLON@29..30 ":"
+                            - TYPE@30..30
+                                - NAMED_TYPE@30..30
+                        - R_CURLY@30..31 "}"
+            - ERROR@65:68 "Expected an InputValue definition here"
+            "#,
+        )
+    }
+
+    #[test]
+    fn it_terminates_on_an_unclosed_block_followed_by_a_sibling_definition() {
+        // A dangling `input` block that's missing its closing `}`, directly
+        // followed by a sibling definition, must not make the stray-token
+        // recovery loop spin forever: the first unexpected token (here,
+        // the `type` keyword starting the sibling) has to be consumed
+        // before the loop re-checks whether it's safe to stop, or the
+        // outer list loop in `input_fields_definition` just keeps
+        // re-entering the same branch with the cursor unmoved. Reaching
+        // the assertions below at all is the regression test: an
+        // unfixed zero-progress loop here hangs instead of returning.
+        let mut p = crate::Parser::new(
+            "input Foo {
+              a: String
+            type Bar {
+              b: String
+            }",
+        );
+        super::input_object_type_definition(&mut p);
+        let (_tree, errors) = p.finish();
+
+        assert!(!errors.is_empty());
+        assert!(errors.iter().all(|e| e.code == "E0016"));
+    }
+
+    #[test]
+    fn it_wraps_a_described_input_object_correctly() {
+        // The leading Description is parsed before this function's own node
+        // kind is decided, so it's wrapped via `precede` (see
+        // `input_object_type_definition`) rather than a plain `p.start()`.
+        // The wrapping INPUT_OBJECT_TYPE_DEFINITION node must still open
+        // before, and close after, the DESCRIPTION it wraps -- not collapse
+        // onto it and finish early, leaving the rest of the definition's
+        // tokens orphaned.
+        utils::check_ast(
+            r#""desc" input Foo { a: String }"#,
+            r#"
+            - DOCUMENT@0..18
+                - INPUT_OBJECT_TYPE_DEFINITION@0..18
+                    - DESCRIPTION@0..6
+                        - STRING_VALUE@0..6 "\"desc\""
+                    - input_KW@6..11 "input"
+                    - NAME@11..14
+                        - IDENT@11..14 "Foo"
+                    - INPUT_FIELDS_DEFINITION@14..18
+                        - L_CURLY@14..15 "{"
+                        - INPUT_VALUE_DEFINITION@15..17
+                            - NAME@15..16
+                                - IDENT@15..16 "a"
+                            - COLON@16..17 ":"
+                            - TYPE@17..17
+                                - NAMED_TYPE@17..17
+                        - R_CURLY@17..18 "}"
             "#,
         )
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,255 @@
+pub(crate) mod grammar;
+pub(crate) mod token_set;
+
+use crate::lexer::{self, Token};
+use crate::{Diagnostic, SyntaxKind, TokenKind};
+
+/// One entry in the parser's flat event buffer.
+///
+/// `Start` is pushed as a tombstone (`kind: None`) and only gets a real
+/// `SyntaxKind` once its `Marker` is completed. `forward_parent` lets a
+/// `Marker` obtained via [`CompletedMarker::precede`] retroactively wrap an
+/// already-completed node in a new enclosing one, for cases where a grammar
+/// function can't know the wrapping node's kind until after it has already
+/// parsed part of it (e.g. an optional leading `Description`).
+enum Event {
+    Start {
+        kind: Option<SyntaxKind>,
+        forward_parent: Option<usize>,
+    },
+    Token {
+        kind: SyntaxKind,
+        text: String,
+    },
+    Finish,
+}
+
+/// A placeholder for a node that hasn't been assigned a kind yet.
+///
+/// Returned by [`Parser::start`]; call [`Marker::complete`] once enough of
+/// the node has been parsed to know what `SyntaxKind` it is.
+#[must_use]
+pub(crate) struct Marker {
+    pos: usize,
+}
+
+impl Marker {
+    fn new(pos: usize) -> Marker {
+        Marker { pos }
+    }
+
+    pub(crate) fn complete(self, p: &mut Parser, kind: SyntaxKind) -> CompletedMarker {
+        match &mut p.events[self.pos] {
+            Event::Start { kind: slot, .. } => *slot = Some(kind),
+            _ => unreachable!("Marker must point at an Event::Start"),
+        }
+        p.events.push(Event::Finish);
+        CompletedMarker { pos: self.pos }
+    }
+}
+
+/// A node whose kind has already been decided, but which can still be
+/// wrapped in a new enclosing node via [`CompletedMarker::precede`].
+pub(crate) struct CompletedMarker {
+    pos: usize,
+}
+
+impl CompletedMarker {
+    /// Opens a new node that starts *before* this one and will finish after
+    /// it, retroactively wrapping it. This is what lets a grammar function
+    /// parse something generic (like an optional `Description`) before it
+    /// knows which enclosing definition follows it.
+    pub(crate) fn precede(self, p: &mut Parser) -> Marker {
+        let new_pos = p.events.len();
+        p.events.push(Event::Start {
+            kind: None,
+            forward_parent: None,
+        });
+        match &mut p.events[self.pos] {
+            Event::Start { forward_parent, .. } => *forward_parent = Some(new_pos),
+            _ => unreachable!("CompletedMarker must point at an Event::Start"),
+        }
+        Marker::new(new_pos)
+    }
+}
+
+/// A hand-written recursive-descent parser over a flat token stream.
+///
+/// Rather than building the tree directly, grammar functions record a flat
+/// [`Event`] buffer (`start` / `bump` / `complete`); [`Parser::finish`] replays
+/// it into a `rowan::GreenNodeBuilder` afterwards. This indirection is what
+/// makes [`CompletedMarker::precede`] possible — a node's wrapping kind can be
+/// decided after the fact, which a direct `GreenNodeBuilder::start_node` call
+/// could never support.
+pub struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    events: Vec<Event>,
+    errors: Vec<Diagnostic>,
+    last_end: usize,
+}
+
+impl Parser {
+    pub fn new(input: &str) -> Parser {
+        Parser {
+            tokens: lexer::lex(input),
+            pos: 0,
+            events: Vec::new(),
+            errors: Vec::new(),
+            last_end: 0,
+        }
+    }
+
+    fn current(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    pub(crate) fn peek(&self) -> Option<TokenKind> {
+        self.current().map(|t| t.kind)
+    }
+
+    /// The `TokenKind` `n` tokens ahead of the current one (`n = 0` is
+    /// `peek`'s own token). Used to disambiguate a contextual keyword from an
+    /// ordinary `Name` in the same position, e.g. a field named `type` (`Name`
+    /// followed by `:`) versus a stray `type` definition keyword (`Name`
+    /// followed by another `Name`).
+    pub(crate) fn nth(&self, n: usize) -> Option<TokenKind> {
+        self.tokens.get(self.pos + n).map(|t| t.kind)
+    }
+
+    pub(crate) fn peek_data(&self) -> Option<String> {
+        self.current().map(|t| t.data.clone())
+    }
+
+    /// The byte offset the current token starts at, or the end of the last
+    /// consumed token if there isn't a current token. Used to anchor diagnostic labels at a
+    /// real source position instead of an arbitrary guess.
+    pub(crate) fn offset(&self) -> usize {
+        match self.current() {
+            Some(tok) => tok.start,
+            None => self.last_end,
+        }
+    }
+
+    /// The byte offset just past the most recently bumped token. Together
+    /// with the offset captured before a run of `bump`/`bump_remap` calls,
+    /// this gives the real span of a multi-token keyword like `extend input`.
+    pub(crate) fn prev_end(&self) -> usize {
+        self.last_end
+    }
+
+    /// Whether the upcoming token is a `Name` whose text is the contextual
+    /// keyword `kw`. GraphQL keywords aren't reserved, so this is the only
+    /// way to recognize one — comparing text, not `TokenKind`.
+    pub(crate) fn at_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Name)) && self.current().unwrap().data == kw
+    }
+
+    pub(crate) fn start(&mut self) -> Marker {
+        let pos = self.events.len();
+        self.events.push(Event::Start {
+            kind: None,
+            forward_parent: None,
+        });
+        Marker::new(pos)
+    }
+
+    fn do_bump(&mut self, kind: SyntaxKind) {
+        let text = self.current().map(|t| t.data.clone()).unwrap_or_default();
+        self.last_end = self.current().map(|t| t.end).unwrap_or(self.last_end);
+        self.events.push(Event::Token { kind, text });
+        self.pos += 1;
+    }
+
+    /// Consumes the current token, labeling it `kind` in the tree.
+    pub(crate) fn bump(&mut self, kind: SyntaxKind) {
+        self.do_bump(kind);
+    }
+
+    /// Consumes a `Name` token that's contextually a keyword, labeling it
+    /// `kind` instead of `SyntaxKind::IDENT` — e.g. the `Name` "input" becomes
+    /// `input_KW` in the tree, without the lexer ever needing to know it's a
+    /// keyword.
+    pub(crate) fn bump_remap(&mut self, kind: SyntaxKind) {
+        self.do_bump(kind);
+    }
+
+    /// Consumes the current token whatever it is, labeling it with its own
+    /// natural `SyntaxKind`. Used by error recovery to skip stray tokens
+    /// without having to name each one.
+    pub(crate) fn bump_any(&mut self) {
+        let kind = self
+            .current()
+            .map(|t| t.kind.into())
+            .unwrap_or(SyntaxKind::ERROR);
+        self.do_bump(kind);
+    }
+
+    pub(crate) fn push_err(&mut self, err: Diagnostic) {
+        self.errors.push(err);
+    }
+
+    /// Runs the event buffer through the tree builder and returns the
+    /// finished green tree alongside any diagnostics collected along the way.
+    pub fn finish(self) -> (rowan::GreenNode, Vec<Diagnostic>) {
+        (build_tree(self.events), self.errors)
+    }
+}
+
+/// Replays a flat `Vec<Event>` into a `rowan::GreenNodeBuilder`, following
+/// `forward_parent` chains so a node can be wrapped by an enclosing one that
+/// was only decided on *after* the node itself was parsed (see
+/// [`CompletedMarker::precede`]).
+fn build_tree(mut events: Vec<Event>) -> rowan::GreenNode {
+    let mut builder = rowan::GreenNodeBuilder::new();
+    let mut forward_parents = Vec::new();
+
+    for i in 0..events.len() {
+        match std::mem::replace(&mut events[i], Event::Finish) {
+            Event::Start {
+                kind: Some(kind),
+                forward_parent,
+            } => {
+                forward_parents.push(kind);
+                let mut next = forward_parent;
+                while let Some(fwd) = next {
+                    // Leave a no-op tombstone behind, not `Event::Finish`:
+                    // this same index is still visited later by the outer
+                    // loop's own natural traversal, and if it were `Finish`
+                    // that visit would call `finish_node()` immediately --
+                    // closing the wrapping node right after the preceded
+                    // node, before the wrapping node's own tokens have even
+                    // been appended.
+                    next = match std::mem::replace(
+                        &mut events[fwd],
+                        Event::Start {
+                            kind: None,
+                            forward_parent: None,
+                        },
+                    ) {
+                        Event::Start {
+                            kind: Some(kind),
+                            forward_parent,
+                        } => {
+                            forward_parents.push(kind);
+                            forward_parent
+                        }
+                        Event::Start { kind: None, .. } => None,
+                        _ => unreachable!("forward_parent must point at an Event::Start"),
+                    };
+                }
+                for kind in forward_parents.drain(..).rev() {
+                    builder.start_node(kind.into());
+                }
+            }
+            Event::Start { kind: None, .. } => {
+                // Tombstone: this Start was folded into a later node via
+                // `forward_parent` and doesn't open anything of its own.
+            }
+            Event::Token { kind, text } => builder.token(kind.into(), &text),
+            Event::Finish => builder.finish_node(),
+        }
+    }
+
+    builder.finish()
+}
@@ -0,0 +1,37 @@
+use crate::TokenKind;
+
+/// A bitset over `TokenKind`, used to describe a set of tokens that are
+/// "safe" to resume parsing from.
+///
+/// This mirrors the recovery-set approach used by rust-analyzer: rather than
+/// reacting to a single unexpected token in isolation, a parser function can
+/// consult a `TokenSet` shared by its callers to decide how far to skip
+/// before giving up and letting an enclosing list/definition continue.
+#[derive(Clone, Copy)]
+pub(crate) struct TokenSet(u128);
+
+impl TokenSet {
+    pub(crate) const EMPTY: TokenSet = TokenSet(0);
+
+    pub(crate) const fn new(kinds: &[TokenKind]) -> TokenSet {
+        let mut bits = 0u128;
+        let mut i = 0;
+        while i < kinds.len() {
+            bits |= mask(kinds[i]);
+            i += 1;
+        }
+        TokenSet(bits)
+    }
+
+    pub(crate) const fn union(self, other: TokenSet) -> TokenSet {
+        TokenSet(self.0 | other.0)
+    }
+
+    pub(crate) fn contains(self, kind: TokenKind) -> bool {
+        self.0 & mask(kind) != 0
+    }
+}
+
+const fn mask(kind: TokenKind) -> u128 {
+    1u128 << (kind as u32)
+}